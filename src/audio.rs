@@ -0,0 +1,56 @@
+use sdl2::audio::{AudioCallback, AudioDevice, AudioSpecDesired};
+
+struct SquareWave {
+    phase_increment: f32,
+    phase: f32,
+    volume: f32,
+}
+
+impl AudioCallback for SquareWave {
+    type Channel = f32;
+
+    fn callback(&mut self, out: &mut [f32]) {
+        for sample in out.iter_mut() {
+            *sample = if self.phase <= 0.5 { self.volume } else { -self.volume };
+            self.phase = (self.phase + self.phase_increment) % 1.0;
+        }
+    }
+}
+
+/// A square-wave beeper driven by a `Processor`'s sound timer: call [`Audio::play`]/[`Audio::stop`]
+/// based on [`crate::processor::Processor::is_sound_active`] from the same place the display is redrawn.
+pub struct Audio {
+    device: AudioDevice<SquareWave>,
+}
+
+impl Audio {
+    pub fn new(sdl_context: &sdl2::Sdl, freq: f32, volume: f32) -> Self {
+        let audio_subsystem = sdl_context.audio().unwrap();
+
+        let spec = AudioSpecDesired {
+            freq: Some(44_100),
+            channels: Some(1),
+            samples: None,
+        };
+
+        let device = audio_subsystem
+            .open_playback(None, &spec, |spec| SquareWave {
+                phase_increment: freq / spec.freq as f32,
+                phase: 0.0,
+                volume,
+            })
+            .unwrap();
+
+        Audio { device }
+    }
+
+    /// Starts playing the tone. No-op if already playing.
+    pub fn play(&self) {
+        self.device.resume();
+    }
+
+    /// Silences the tone. No-op if already silent.
+    pub fn stop(&self) {
+        self.device.pause();
+    }
+}
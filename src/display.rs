@@ -4,19 +4,25 @@ use sdl2::pixels;
 use sdl2::rect::Rect;
 
 
-const CHIP8_WIDTH: usize = 64;
-const CHIP8_HEIGHT: usize = 32;
-const SCALE_FACTOR: u32 = 20;
+const HIRES_WIDTH: usize = 128;
+const HIRES_HEIGHT: usize = 64;
+const SCALE_FACTOR: u32 = 10;
 
-const SCREEN_WIDTH: u32 = (CHIP8_WIDTH as u32) * SCALE_FACTOR;
-const SCREEN_HEIGHT: u32 = (CHIP8_HEIGHT as u32) * SCALE_FACTOR;
+const SCREEN_WIDTH: u32 = (HIRES_WIDTH as u32) * SCALE_FACTOR;
+const SCREEN_HEIGHT: u32 = (HIRES_HEIGHT as u32) * SCALE_FACTOR;
 
 pub struct Display {
     canvas: Canvas<Window>,
+    foreground: pixels::Color,
+    background: pixels::Color,
 }
 
 impl Display {
     pub fn new(sdl_context: &sdl2::Sdl) -> Self {
+        Self::with_palette(sdl_context, pixels::Color::RGB(0, 250, 0), pixels::Color::RGB(0, 0, 0))
+    }
+
+    pub fn with_palette(sdl_context: &sdl2::Sdl, foreground: pixels::Color, background: pixels::Color) -> Self {
         let video_subsys = sdl_context.video().unwrap();
         let window = video_subsys
             .window(
@@ -31,32 +37,39 @@ impl Display {
 
         let mut canvas = window.into_canvas().build().unwrap();
 
-        canvas.set_draw_color(pixels::Color::RGB(0, 0, 0));
+        canvas.set_draw_color(background);
         canvas.clear();
         canvas.present();
 
-        Display { canvas }
+        Display { canvas, foreground, background }
     }
 
-    pub fn draw(&mut self, pixels: &[[u8; CHIP8_WIDTH]; CHIP8_HEIGHT]) {
+    /// Draws a frame at whatever resolution the emulator is currently running at (64x32 lores or
+    /// 128x64 SCHIP hires), scaling pixels up to fill the window either way.
+    pub fn draw(&mut self, pixels: &[Vec<u8>]) {
+        let height = pixels.len().max(1) as u32;
+        let width = pixels.get(0).map_or(1, |row| row.len()).max(1) as u32;
+        let scale_x = SCREEN_WIDTH / width;
+        let scale_y = SCREEN_HEIGHT / height;
+
         for (y, row) in pixels.iter().enumerate() {
             for (x, &col) in row.iter().enumerate() {
-                let x = (x as u32) * SCALE_FACTOR;
-                let y = (y as u32) * SCALE_FACTOR;
+                let x = (x as u32) * scale_x;
+                let y = (y as u32) * scale_y;
 
-                self.canvas.set_draw_color(color(col));
+                self.canvas.set_draw_color(self.color(col));
                 let _ = self.canvas
-                    .fill_rect(Rect::new(x as i32, y as i32, SCALE_FACTOR, SCALE_FACTOR));
+                    .fill_rect(Rect::new(x as i32, y as i32, scale_x, scale_y));
             }
         }
         self.canvas.present();
     }
-}
 
-fn color(value: u8) -> pixels::Color {
-    if value == 0 {
-        pixels::Color::RGB(0, 0, 0)
-    } else {
-        pixels::Color::RGB(0, 250, 0)
+    fn color(&self, value: u8) -> pixels::Color {
+        if value == 0 {
+            self.background
+        } else {
+            self.foreground
+        }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,74 @@
+use sdl2::event::Event;
+use sdl2::keyboard::Scancode;
+use sdl2::EventPump;
+use std::collections::{HashMap, HashSet};
+
+const KEYPAD_SIZE: usize = 16;
+
+/// One frame's worth of input: the held-down keypad state used to drive opcodes, plus any
+/// non-keypad scancodes that went down this frame (edge-triggered, so a held hotkey fires once).
+pub struct Frame {
+    pub keypad: [bool; KEYPAD_SIZE],
+    pub pressed: HashSet<Scancode>,
+}
+
+pub struct Input {
+    event_pump: EventPump,
+    keymap: HashMap<Scancode, usize>,
+}
+
+impl Input {
+    pub fn new(sdl_context: &sdl2::Sdl) -> Self {
+        Self::with_keymap(sdl_context, default_keymap())
+    }
+
+    /// Builds an `Input` that maps host `Scancode`s to CHIP-8 keypad positions (0x0-0xF)
+    /// according to `keymap`, instead of the default COSMAC VIP-on-QWERTY layout.
+    pub fn with_keymap(sdl_context: &sdl2::Sdl, keymap: HashMap<Scancode, usize>) -> Self {
+        let event_pump = sdl_context.event_pump().unwrap();
+
+        Input { event_pump, keymap }
+    }
+
+    /// Drains pending SDL events and returns the current frame's input, or `Err(())` once the
+    /// user has requested to quit.
+    pub fn poll(&mut self) -> Result<Frame, ()> {
+        let mut pressed = HashSet::new();
+
+        for event in self.event_pump.poll_iter() {
+            match event {
+                Event::Quit { .. } => return Err(()),
+                Event::KeyDown { scancode: Some(scancode), repeat: false, .. }
+                    if !self.keymap.contains_key(&scancode) =>
+                {
+                    pressed.insert(scancode);
+                }
+                _ => {}
+            }
+        }
+
+        let keyboard_state = self.event_pump.keyboard_state();
+        let mut keypad = [false; KEYPAD_SIZE];
+
+        for (&scancode, &position) in self.keymap.iter() {
+            if keyboard_state.is_scancode_pressed(scancode) {
+                keypad[position] = true;
+            }
+        }
+
+        Ok(Frame { keypad, pressed })
+    }
+}
+
+/// The classic COSMAC VIP keypad laid out over the left-hand side of a QWERTY keyboard:
+/// `1234` / `QWER` / `ASDF` / `ZXCV` -> `123C` / `456D` / `789E` / `A0BF`.
+fn default_keymap() -> HashMap<Scancode, usize> {
+    use Scancode::*;
+
+    HashMap::from([
+        (Num1, 0x1), (Num2, 0x2), (Num3, 0x3), (Num4, 0xC),
+        (Q, 0x4), (W, 0x5), (E, 0x6), (R, 0xD),
+        (A, 0x7), (S, 0x8), (D, 0x9), (F, 0xE),
+        (Z, 0xA), (X, 0x0), (C, 0xB), (V, 0xF),
+    ])
+}
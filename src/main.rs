@@ -1,14 +1,25 @@
-use std::{env, thread};
+use std::{env, fs, thread};
 use crate::processor::Processor;
 use crate::display::Display;
 use crate::input::Input;
-use std::borrow::Borrow;
+use crate::audio::Audio;
+use sdl2::keyboard::Scancode;
+use std::collections::HashSet;
 use std::time::Duration;
 
 mod processor;
 mod font;
 mod display;
 mod input;
+mod audio;
+
+// Run several CPU cycles per frame but decrement the delay/sound timers only once per frame, so
+// timed games run at the correct speed regardless of how many opcodes execute per frame.
+const CYCLES_PER_FRAME: u32 = 10;
+const FRAME_DURATION: Duration = Duration::from_millis(16);
+
+// CHIP-8's fixed memory size, used here only to clamp the debug memory dump below.
+const MEMORY_SIZE: usize = 4096;
 
 fn main() {
     let sdl_context = match sdl2::init() {
@@ -18,19 +29,108 @@ fn main() {
 
     let mut display = Display::new(&sdl_context);
     let mut input = Input::new(&sdl_context);
+    let audio = Audio::new(&sdl_context, 440.0, 0.25);
 
     let args: Vec<String> = env::args().collect();
     let rom_filename = &args[1];
+    let state_filename = format!("{}.state", rom_filename);
 
     let mut processor = Processor::new();
-    processor.load_rom(String::from(rom_filename));
+    if let Err(err) = processor.load_rom(rom_filename) {
+        panic!("Unable to load rom {}: {:?}", rom_filename, err);
+    }
+
+    let mut paused = false;
+    let mut breakpoints: HashSet<u16> = HashSet::new();
+
+    while let Ok(frame) = input.poll() {
+        if frame.pressed.contains(&Scancode::F5) {
+            if let Err(err) = fs::write(&state_filename, processor.to_bytes()) {
+                eprintln!("Unable to save state to {}: {:?}", state_filename, err);
+            }
+        }
+
+        if frame.pressed.contains(&Scancode::F9) {
+            match fs::read(&state_filename).map(|bytes| Processor::from_bytes(&bytes)) {
+                Ok(Ok(restored)) => processor = restored,
+                Ok(Err(err)) => eprintln!("Unable to parse state {}: {:?}", state_filename, err),
+                Err(err) => eprintln!("Unable to read state {}: {:?}", state_filename, err),
+            }
+        }
+
+        if frame.pressed.contains(&Scancode::P) {
+            paused = !paused;
+        }
+
+        if paused {
+            if frame.pressed.contains(&Scancode::N) {
+                match processor.step() {
+                    Ok(trace) => {
+                        println!("{:?}", trace);
+                        println!(
+                            "registers={:?} index={:#06x} sp={} dt={} st={}",
+                            processor.registers(),
+                            processor.index(),
+                            processor.sp(),
+                            processor.delay_timer(),
+                            processor.sound_timer(),
+                        );
+                        println!("history={:?}", processor.history());
+                    }
+                    Err(err) => panic!("Unable to execute opcode: {:?}", err),
+                }
+            }
+
+            if frame.pressed.contains(&Scancode::B) {
+                let pc = processor.pc();
+                if breakpoints.remove(&pc) {
+                    processor.remove_breakpoint(pc);
+                    println!("Removed breakpoint at {:#06x}", pc);
+                } else {
+                    breakpoints.insert(pc);
+                    processor.add_breakpoint(pc);
+                    println!("Added breakpoint at {:#06x}", pc);
+                }
+            }
+
+            if frame.pressed.contains(&Scancode::G) {
+                match processor.run_until_break() {
+                    Ok(trace) => {
+                        println!("Hit breakpoint: {:?}", trace);
+                        let pc = processor.pc() as usize;
+                        let end = (pc + 8).min(MEMORY_SIZE);
+                        println!(
+                            "registers={:?} index={:#06x} sp={} stack={:?} memory[{:#06x}..{:#06x}]={:?}",
+                            processor.registers(),
+                            processor.index(),
+                            processor.sp(),
+                            processor.stack(),
+                            pc,
+                            end,
+                            processor.memory_slice(pc..end),
+                        );
+                    }
+                    Err(err) => panic!("Unable to execute opcode: {:?}", err),
+                }
+            }
+        } else {
+            for _ in 0..CYCLES_PER_FRAME {
+                if let Err(err) = processor.tick(frame.keypad) {
+                    panic!("Unable to execute opcode: {:?}", err);
+                }
+            }
+            processor.tick_timers();
+        }
 
-    while let Ok(keypad) = input.poll() {
-        processor.tick(keypad);
+        if processor.is_sound_active() {
+            audio.play();
+        } else {
+            audio.stop();
+        }
 
-        display.draw(processor.video.borrow());
+        display.draw(&processor.video);
 
-        thread::sleep(Duration::from_millis(1));
+        thread::sleep(FRAME_DURATION);
     }
 
 }
@@ -1,4 +1,5 @@
 use rand::Rng;
+use std::collections::HashSet;
 use std::path::Path;
 use std::fs::File;
 use std::io::Read;
@@ -15,11 +16,106 @@ const KEYPAD_SIZE: usize = 16;
 const SCREEN_WIDTH: usize = 64;
 const SCREEN_HEIGHT: usize = 32;
 
+const HIRES_SCREEN_WIDTH: usize = 128;
+const HIRES_SCREEN_HEIGHT: usize = 64;
+
 const CARRY_REGISTER: usize = 0xF;
 
 const FONT_CHARACTER_BYTES: u8 = 5;
 
+const BIG_FONTSET_START_ADDRESS: u16 = 0xA0;
+const BIG_FONT_CHARACTER_BYTES: u8 = 10;
+
+/// RPL "flag" persistent storage used by SCHIP's `FX75`/`FX85`.
+const RPL_FLAGS_SIZE: usize = 16;
+
+/// 8x10 "large" digit glyphs used by SCHIP's `FX30`, stored in memory right after `FONTSET`.
+const BIG_FONTSET: [u8; 160] = [
+    0x3C, 0x7E, 0xE7, 0xC3, 0xC3, 0xC3, 0xC3, 0xE7, 0x7E, 0x3C, // 0
+    0x18, 0x38, 0x58, 0x18, 0x18, 0x18, 0x18, 0x18, 0x18, 0x3C, // 1
+    0x7E, 0xFF, 0x03, 0x03, 0x07, 0x1E, 0x38, 0x70, 0xFF, 0xFF, // 2
+    0x7E, 0xFF, 0x03, 0x03, 0x3E, 0x03, 0x03, 0x03, 0xFF, 0x7E, // 3
+    0x06, 0x0E, 0x1E, 0x36, 0x66, 0xC6, 0xFF, 0xFF, 0x06, 0x06, // 4
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFE, 0xFF, 0x03, 0x03, 0xFF, 0xFE, // 5
+    0x7E, 0xFF, 0xC0, 0xC0, 0xFE, 0xFF, 0xC3, 0xC3, 0xFF, 0x7E, // 6
+    0xFF, 0xFF, 0x03, 0x06, 0x0C, 0x18, 0x30, 0x30, 0x30, 0x30, // 7
+    0x7E, 0xFF, 0xC3, 0xC3, 0x7E, 0x7E, 0xC3, 0xC3, 0xFF, 0x7E, // 8
+    0x7E, 0xFF, 0xC3, 0xC3, 0xFF, 0x7F, 0x03, 0x03, 0xFF, 0x7E, // 9
+    0x18, 0x3C, 0x66, 0xC3, 0xC3, 0xFF, 0xFF, 0xC3, 0xC3, 0xC3, // A
+    0xFC, 0xFE, 0xC3, 0xC3, 0xFE, 0xFC, 0xC3, 0xC3, 0xFE, 0xFC, // B
+    0x3C, 0x7E, 0xC3, 0xC0, 0xC0, 0xC0, 0xC0, 0xC3, 0x7E, 0x3C, // C
+    0xFC, 0xFE, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xC3, 0xFE, 0xFC, // D
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xFF, 0xFF, // E
+    0xFF, 0xFF, 0xC0, 0xC0, 0xFC, 0xFC, 0xC0, 0xC0, 0xC0, 0xC0, // F
+];
+
+/// Errors produced while loading a ROM or decoding/executing an opcode.
+#[derive(Debug)]
+pub enum Chip8Error {
+    /// No handler matches the given opcode.
+    BadInstruction(u16),
+    /// The ROM file couldn't be opened or read.
+    Io(std::io::Error),
+    /// The ROM doesn't fit in the space left after the font set and reserved interpreter area.
+    RomTooLarge { size: usize, capacity: usize },
+    /// An opcode tried to read or write memory past `MEMORY_SIZE`.
+    AddressOutOfBounds(u16),
+    /// `00EE` (`RET`) was executed with an empty call stack.
+    StackUnderflow,
+    /// `2NNN` (`CALL`) was executed with the call stack already `STACK_SIZE` deep.
+    StackOverflow,
+}
+
+/// Errors produced while restoring a machine state saved by [`Processor::to_bytes`].
+#[derive(Debug)]
+pub enum StateError {
+    /// The blob doesn't start with the expected magic bytes.
+    BadMagic,
+    /// The blob was produced by an incompatible version of [`Processor::to_bytes`].
+    UnsupportedVersion(u8),
+    /// The blob is shorter than the header or a field declares.
+    Truncated,
+    /// The stored video dimensions aren't one of the two supported resolutions (64x32 lores,
+    /// 128x64 SCHIP hires).
+    InvalidDimensions { width: usize, height: usize },
+}
+
+const STATE_MAGIC: &[u8; 4] = b"CPST";
+const STATE_VERSION: u8 = 1;
+
+/// How many executed `(pc, opcode)` pairs [`Processor::step`] keeps around for
+/// [`Processor::history`], so a paused session can print recent history for crash backtracing.
+const HISTORY_CAPACITY: usize = 64;
+
+/// Toggles for the well-known ambiguous CHIP-8 behaviors that different interpreters disagree
+/// on, so the same `Processor` can run both classic COSMAC VIP ROMs and later SCHIP-era ones.
+#[derive(Debug, Clone, Copy)]
+pub struct Quirks {
+    /// `8xy6`/`8xyE` shift `Vx` in place (`false`, COSMAC VIP) vs. copy `Vy` into `Vx` first (`true`, SCHIP).
+    pub shift: bool,
+    /// `Fx55`/`Fx65` leave `index` unchanged (`false`, COSMAC VIP) vs. advance it by `x + 1` (`true`).
+    pub load_store: bool,
+    /// `Bnnn` jumps to `nnn + V0` (`false`, COSMAC VIP) vs. `BXNN` jumps to `xnn + Vx` (`true`).
+    pub jump: bool,
+    /// `8xy1`/`8xy2`/`8xy3` leave VF untouched (`false`, classic) vs. reset it to 0 (`true`, COSMAC VIP).
+    pub vf_reset: bool,
+    /// `Dxyn` wraps sprites around screen edges (`false`, COSMAC VIP) vs. clips them (`true`, SCHIP).
+    pub clipping: bool,
+}
 
+impl Default for Quirks {
+    fn default() -> Self {
+        Quirks {
+            shift: false,
+            load_store: false,
+            jump: false,
+            vf_reset: false,
+            clipping: false,
+        }
+    }
+}
+
+#[derive(Clone)]
 pub struct Processor {
     registers: [u8; REGISTERS_SIZE],
     memory: [u8; MEMORY_SIZE],
@@ -30,18 +126,175 @@ pub struct Processor {
     delay_timer: u8,
     sound_timer: u8,
     keypad: [bool; KEYPAD_SIZE],
-    pub(crate) video: [[u8; SCREEN_WIDTH]; SCREEN_HEIGHT],
-    opcode: u16
+    pub(crate) video: Vec<Vec<u8>>,
+    hires: bool,
+    rpl_flags: [u8; RPL_FLAGS_SIZE],
+    opcode: u16,
+    quirks: Quirks,
+    halted: bool,
+    breakpoints: HashSet<u16>,
+    // Ring buffer of the last `HISTORY_CAPACITY` `(pc, opcode)` pairs executed by `step`.
+    history: [(u16, u16); HISTORY_CAPACITY],
+    history_next: usize,
+    history_len: usize,
+}
+
+/// One executed instruction's effect on visible CPU state, returned by [`Processor::step`] for
+/// driving a debugger UI.
+#[derive(Debug, Clone)]
+pub struct TraceRecord {
+    pub pc: u16,
+    pub opcode: u16,
+    pub mnemonic: String,
+    pub changed_registers: Vec<(u8, u8)>,
+    pub index: u16,
+    pub sp: u8,
+}
+
+/// A point-in-time copy of a [`Processor`]'s core machine state, returned by
+/// [`Processor::snapshot`] and restorable via [`Processor::restore`]. Useful for building a
+/// rewind ring-buffer, or for serializing to disk with [`ProcessorState::to_bytes`].
+#[derive(Debug, Clone)]
+pub struct ProcessorState {
+    registers: [u8; REGISTERS_SIZE],
+    memory: [u8; MEMORY_SIZE],
+    index: u16,
+    pc: u16,
+    stack: [u16; STACK_SIZE],
+    sp: u8,
+    delay_timer: u8,
+    sound_timer: u8,
+    video: Vec<Vec<u8>>,
+    opcode: u16,
+}
+
+impl ProcessorState {
+    /// Serializes this state into a flat, versioned byte blob that
+    /// [`ProcessorState::from_bytes`] can restore later.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+
+        buf.extend_from_slice(STATE_MAGIC);
+        buf.push(STATE_VERSION);
+
+        write_field(&mut buf, &self.registers);
+        write_field(&mut buf, &self.memory);
+        write_field(&mut buf, &self.index.to_be_bytes());
+        write_field(&mut buf, &self.pc.to_be_bytes());
+
+        let mut stack_bytes = [0u8; STACK_SIZE * 2];
+        for (i, value) in self.stack.iter().enumerate() {
+            stack_bytes[i * 2..i * 2 + 2].copy_from_slice(&value.to_be_bytes());
+        }
+        write_field(&mut buf, &stack_bytes);
+
+        write_field(&mut buf, &[self.sp]);
+        write_field(&mut buf, &[self.delay_timer]);
+        write_field(&mut buf, &[self.sound_timer]);
+
+        let height = self.video.len();
+        let width = self.video.get(0).map_or(0, |row| row.len());
+        write_field(&mut buf, &(height as u16).to_be_bytes());
+        write_field(&mut buf, &(width as u16).to_be_bytes());
+
+        let mut video_bytes = Vec::with_capacity(width * height);
+        for row in self.video.iter() {
+            video_bytes.extend_from_slice(row);
+        }
+        write_field(&mut buf, &video_bytes);
+
+        write_field(&mut buf, &self.opcode.to_be_bytes());
+
+        buf
+    }
+
+    /// Restores a state previously produced by [`ProcessorState::to_bytes`], rejecting anything
+    /// truncated or from an incompatible version instead of panicking.
+    pub fn from_bytes(bytes: &[u8]) -> Result<ProcessorState, StateError> {
+        if bytes.len() < STATE_MAGIC.len() + 1 {
+            return Err(StateError::Truncated);
+        }
+
+        if &bytes[0..STATE_MAGIC.len()] != STATE_MAGIC {
+            return Err(StateError::BadMagic);
+        }
+
+        let version = bytes[STATE_MAGIC.len()];
+        if version != STATE_VERSION {
+            return Err(StateError::UnsupportedVersion(version));
+        }
+
+        let mut cursor = STATE_MAGIC.len() + 1;
+
+        let registers = read_field(bytes, &mut cursor, REGISTERS_SIZE)?;
+        let memory = read_field(bytes, &mut cursor, MEMORY_SIZE)?;
+        let index = read_field(bytes, &mut cursor, 2)?;
+        let pc = read_field(bytes, &mut cursor, 2)?;
+        let stack = read_field(bytes, &mut cursor, STACK_SIZE * 2)?;
+        let sp = read_field(bytes, &mut cursor, 1)?;
+        let delay_timer = read_field(bytes, &mut cursor, 1)?;
+        let sound_timer = read_field(bytes, &mut cursor, 1)?;
+
+        let height = read_field(bytes, &mut cursor, 2)?;
+        let height = u16::from_be_bytes([height[0], height[1]]) as usize;
+        let width = read_field(bytes, &mut cursor, 2)?;
+        let width = u16::from_be_bytes([width[0], width[1]]) as usize;
+
+        if (width, height) != (SCREEN_WIDTH, SCREEN_HEIGHT)
+            && (width, height) != (HIRES_SCREEN_WIDTH, HIRES_SCREEN_HEIGHT)
+        {
+            return Err(StateError::InvalidDimensions { width, height });
+        }
+
+        let video = read_field(bytes, &mut cursor, width * height)?;
+
+        let opcode = read_field(bytes, &mut cursor, 2)?;
+
+        let mut registers_arr = [0u8; REGISTERS_SIZE];
+        registers_arr.copy_from_slice(registers);
+
+        let mut memory_arr = [0u8; MEMORY_SIZE];
+        memory_arr.copy_from_slice(memory);
+
+        let mut stack_arr = [0u16; STACK_SIZE];
+        for (i, chunk) in stack.chunks(2).enumerate() {
+            stack_arr[i] = u16::from_be_bytes([chunk[0], chunk[1]]);
+        }
+
+        let video = video.chunks(width).map(|row| row.to_vec()).collect();
+
+        Ok(ProcessorState {
+            registers: registers_arr,
+            memory: memory_arr,
+            index: u16::from_be_bytes([index[0], index[1]]),
+            pc: u16::from_be_bytes([pc[0], pc[1]]),
+            stack: stack_arr,
+            sp: sp[0],
+            delay_timer: delay_timer[0],
+            sound_timer: sound_timer[0],
+            video,
+            opcode: u16::from_be_bytes([opcode[0], opcode[1]]),
+        })
+    }
 }
 
 impl Processor {
     pub fn new() -> Processor {
+        Processor::with_quirks(Quirks::default())
+    }
+
+    /// Builds a `Processor` with the given compatibility [`Quirks`] instead of the defaults.
+    pub fn with_quirks(quirks: Quirks) -> Processor {
         let mut memory = [0; MEMORY_SIZE];
 
         for i in 0..FONTSET.len() {
             memory[FONTSET_START_ADDRESS as usize + i] = FONTSET[i];
         }
 
+        for i in 0..BIG_FONTSET.len() {
+            memory[BIG_FONTSET_START_ADDRESS as usize + i] = BIG_FONTSET[i];
+        }
+
         Processor {
             registers: [0; REGISTERS_SIZE],
             memory,
@@ -52,34 +305,227 @@ impl Processor {
             delay_timer: 0,
             sound_timer: 0,
             keypad: [false; KEYPAD_SIZE],
-            video: [[0; SCREEN_WIDTH]; SCREEN_HEIGHT],
-            opcode: 0
+            video: vec![vec![0; SCREEN_WIDTH]; SCREEN_HEIGHT],
+            hires: false,
+            rpl_flags: [0; RPL_FLAGS_SIZE],
+            opcode: 0,
+            quirks,
+            halted: false,
+            breakpoints: HashSet::new(),
+            history: [(0, 0); HISTORY_CAPACITY],
+            history_next: 0,
+            history_len: 0,
         }
     }
 
-    pub fn load_rom(&mut self, filename: String) {
-        let path = Path::new(&filename);
+    /// Width of the current display, 64 (lores) or 128 once `00FF` has switched into SCHIP hires mode.
+    pub fn width(&self) -> usize {
+        if self.hires { HIRES_SCREEN_WIDTH } else { SCREEN_WIDTH }
+    }
 
-        let mut file = match File::open(&path) {
-            Err(why) => panic!("Unable to open rom {}: {}", path.display(), why),
-            Ok(file) => file,
-        };
+    /// Height of the current display, 32 (lores) or 64 once `00FF` has switched into SCHIP hires mode.
+    pub fn height(&self) -> usize {
+        if self.hires { HIRES_SCREEN_HEIGHT } else { SCREEN_HEIGHT }
+    }
 
-        let mut rom_vector: Vec<u8> = Vec::new();
+    /// Current display resolution as `(width, height)`, driven by the `00FE`/`00FF` opcodes.
+    pub fn resolution(&self) -> (usize, usize) {
+        (self.width(), self.height())
+    }
+
+    /// Captures the current machine state as a standalone [`ProcessorState`], e.g. to push onto
+    /// a rewind ring-buffer.
+    pub fn snapshot(&self) -> ProcessorState {
+        ProcessorState {
+            registers: self.registers,
+            memory: self.memory,
+            index: self.index,
+            pc: self.pc,
+            stack: self.stack,
+            sp: self.sp,
+            delay_timer: self.delay_timer,
+            sound_timer: self.sound_timer,
+            video: self.video.clone(),
+            opcode: self.opcode,
+        }
+    }
+
+    /// Restores a previously captured [`ProcessorState`], overwriting the current machine state.
+    pub fn restore(&mut self, state: &ProcessorState) {
+        self.registers = state.registers;
+        self.memory = state.memory;
+        self.index = state.index;
+        self.pc = state.pc;
+        self.stack = state.stack;
+        self.sp = state.sp;
+        self.delay_timer = state.delay_timer;
+        self.sound_timer = state.sound_timer;
+        self.hires = state.video.len() == HIRES_SCREEN_HEIGHT;
+        self.video = state.video.clone();
+        self.opcode = state.opcode;
+    }
+
+    /// Serializes the current machine state into a flat, versioned byte blob that
+    /// [`Processor::from_bytes`] can restore later, for writing save-states to disk.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        self.snapshot().to_bytes()
+    }
+
+    /// Builds a `Processor` from a byte blob previously produced by [`Processor::to_bytes`].
+    /// Registers, memory, and video are restored; quirks and input state start at their defaults.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Processor, StateError> {
+        let state = ProcessorState::from_bytes(bytes)?;
+
+        let mut processor = Processor::new();
+        processor.restore(&state);
+
+        Ok(processor)
+    }
+
+    /// Executes one instruction and reports what changed, for driving a debugger UI.
+    pub fn step(&mut self) -> Result<TraceRecord, Chip8Error> {
+        let pc = self.pc;
+        let registers_before = self.registers;
+
+        self.opcode = self.get_opcode()?;
+        let opcode = self.opcode;
+        let mnemonic = disassemble(opcode);
+
+        self.pc += 2;
+        self.run_opcode()?;
+
+        let changed_registers = registers_before
+            .iter()
+            .zip(self.registers.iter())
+            .enumerate()
+            .filter(|(_, (before, after))| before != after)
+            .map(|(i, (_, &after))| (i as u8, after))
+            .collect();
+
+        self.history[self.history_next] = (pc, opcode);
+        self.history_next = (self.history_next + 1) % HISTORY_CAPACITY;
+        self.history_len = (self.history_len + 1).min(HISTORY_CAPACITY);
+
+        Ok(TraceRecord {
+            pc,
+            opcode,
+            mnemonic,
+            changed_registers,
+            index: self.index,
+            sp: self.sp,
+        })
+    }
+
+    /// Returns the `(pc, opcode)` pairs recorded by [`Processor::step`] in execution order,
+    /// oldest first. Holds at most `HISTORY_CAPACITY` entries; older ones are overwritten.
+    pub fn history(&self) -> Vec<(u16, u16)> {
+        let oldest = (self.history_next + HISTORY_CAPACITY - self.history_len) % HISTORY_CAPACITY;
+
+        (0..self.history_len)
+            .map(|i| self.history[(oldest + i) % HISTORY_CAPACITY])
+            .collect()
+    }
+
+    /// Current register file, for a debugger UI.
+    pub fn registers(&self) -> &[u8; REGISTERS_SIZE] {
+        &self.registers
+    }
+
+    /// A read-only view of `range` within memory, for a debugger UI.
+    pub fn memory_slice(&self, range: std::ops::Range<usize>) -> &[u8] {
+        &self.memory[range]
+    }
+
+    /// Current program counter.
+    pub fn pc(&self) -> u16 {
+        self.pc
+    }
+
+    /// Current index (I) register.
+    pub fn index(&self) -> u16 {
+        self.index
+    }
+
+    /// Current call stack (only the first `sp` entries are in use).
+    pub fn stack(&self) -> &[u16; STACK_SIZE] {
+        &self.stack
+    }
+
+    /// Current call stack depth.
+    pub fn sp(&self) -> u8 {
+        self.sp
+    }
+
+    /// Current delay timer value.
+    pub fn delay_timer(&self) -> u8 {
+        self.delay_timer
+    }
+
+    /// Current sound timer value.
+    pub fn sound_timer(&self) -> u8 {
+        self.sound_timer
+    }
+
+    /// Adds a software breakpoint at `address`; [`Processor::run_until_break`] stops once `pc`
+    /// reaches it.
+    pub fn add_breakpoint(&mut self, address: u16) {
+        self.breakpoints.insert(address);
+    }
+
+    /// Removes a previously added breakpoint. No-op if it wasn't set.
+    pub fn remove_breakpoint(&mut self, address: u16) {
+        self.breakpoints.remove(&address);
+    }
+
+    /// Steps repeatedly until `pc` reaches a breakpoint, returning the trace of the instruction
+    /// that landed on it.
+    pub fn run_until_break(&mut self) -> Result<TraceRecord, Chip8Error> {
+        loop {
+            let trace = self.step()?;
 
-        match file.read_to_end(&mut rom_vector) {
-            Ok(_) => {},
-            Err(why) => panic!("Unable to read rom: {}", why)
+            if self.breakpoints.contains(&self.pc) {
+                return Ok(trace);
+            }
         }
+    }
 
-        for i in 0..rom_vector.len() {
-            self.memory[ROM_START_ADDRESS as usize + i] = *rom_vector.get(i).expect("Unable to read from ROM vector");
+    /// Loads a ROM from `path` into memory right after the reserved interpreter area, rejecting
+    /// files that don't fit or can't be read instead of panicking.
+    pub fn load_rom<P: AsRef<Path>>(&mut self, path: P) -> Result<(), Chip8Error> {
+        let mut file = File::open(path).map_err(Chip8Error::Io)?;
+
+        let mut rom_vector: Vec<u8> = Vec::new();
+        file.read_to_end(&mut rom_vector).map_err(Chip8Error::Io)?;
+
+        let capacity = MEMORY_SIZE - ROM_START_ADDRESS as usize;
+        if rom_vector.len() > capacity {
+            return Err(Chip8Error::RomTooLarge { size: rom_vector.len(), capacity });
         }
+
+        let start = ROM_START_ADDRESS as usize;
+        self.memory[start..start + rom_vector.len()].copy_from_slice(&rom_vector);
+
+        Ok(())
     }
 
-    pub fn tick(&mut self, keypad: [bool; KEYPAD_SIZE]) {
+    pub fn tick(&mut self, keypad: [bool; KEYPAD_SIZE]) -> Result<(), Chip8Error> {
+        if self.halted {
+            return Ok(());
+        }
+
         self.keypad = keypad;
 
+        self.opcode = self.get_opcode()?;
+
+        self.pc += 2;
+
+        self.run_opcode()
+    }
+
+    /// Decrements the delay and sound timers by one. Unlike [`Processor::tick`], which fetches
+    /// and executes as fast as the frontend drives it, this should be called at a fixed 60 Hz
+    /// regardless of CPU speed so timed games run at the correct real-world rate.
+    pub fn tick_timers(&mut self) {
         if self.delay_timer > 0 {
             self.delay_timer -= 1
         }
@@ -87,19 +533,24 @@ impl Processor {
         if self.sound_timer > 0 {
             self.sound_timer -= 1
         }
+    }
 
-        self.opcode = self.get_opcode();
+    /// Whether the sound timer is currently active, so a frontend can gate a beep on or off.
+    pub fn is_sound_active(&self) -> bool {
+        self.sound_timer > 0
+    }
 
-        self.pc += 2;
+    fn get_opcode(&self) -> Result<u16, Chip8Error> {
+        let pc = self.pc as usize;
 
-        self.run_opcode();
-    }
+        if pc + 1 >= MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.pc));
+        }
 
-    fn get_opcode(&self) -> u16 {
-        (self.memory[self.pc as usize] as u16) << 8 | (self.memory[(self.pc + 1) as usize] as u16)
+        Ok((self.memory[pc] as u16) << 8 | (self.memory[pc + 1] as u16))
     }
 
-    fn run_opcode(&mut self) {
+    fn run_opcode(&mut self) -> Result<(), Chip8Error> {
         let params = (
             (self.opcode & 0xF000) >> 12 as u8,
             (self.opcode & 0x0F00) >> 8 as u8,
@@ -114,10 +565,16 @@ impl Processor {
         let n = params.3 as usize;
 
         match params {
+            (0x00, 0x00, 0x0c, _) => self.op_00cn(n),
             (0x00, 0x00, 0x0e, 0x00) => self.op_00e0(),
-            (0x00, 0x00, 0x0e, 0x0e) => self.op_00ee(),
+            (0x00, 0x00, 0x0e, 0x0e) => self.op_00ee()?,
+            (0x00, 0x00, 0x0f, 0x0b) => self.op_00fb(),
+            (0x00, 0x00, 0x0f, 0x0c) => self.op_00fc(),
+            (0x00, 0x00, 0x0f, 0x0d) => self.op_00fd(),
+            (0x00, 0x00, 0x0f, 0x0e) => self.op_00fe(),
+            (0x00, 0x00, 0x0f, 0x0f) => self.op_00ff(),
             (0x01, _, _, _) => self.op_1nnn(nnn),
-            (0x02, _, _, _) => self.op_2nnn(nnn),
+            (0x02, _, _, _) => self.op_2nnn(nnn)?,
             (0x03, _, _, _) => self.op_3xkk(x, kk),
             (0x04, _, _, _) => self.op_4xkk(x, kk),
             (0x05, _, _, 0x00) => self.op_5xy0(x, y),
@@ -129,14 +586,14 @@ impl Processor {
             (0x08, _, _, 0x03) => self.op_8xy3(x, y),
             (0x08, _, _, 0x04) => self.op_8xy4(x, y),
             (0x08, _, _, 0x05) => self.op_8xy5(x, y),
-            (0x08, _, _, 0x06) => self.op_8xy6(x),
+            (0x08, _, _, 0x06) => self.op_8xy6(x, y),
             (0x08, _, _, 0x07) => self.op_8xy7(x, y),
-            (0x08, _, _, 0x0e) => self.op_8xye(x),
+            (0x08, _, _, 0x0e) => self.op_8xye(x, y),
             (0x09, _, _, 0x00) => self.op_9xy0(x, y),
             (0x0a, _, _, _) => self.op_annn(nnn),
-            (0x0b, _, _, _) => self.op_bnnn(nnn),
+            (0x0b, _, _, _) => self.op_bnnn(x, nnn),
             (0x0c, _, _, _) => self.op_cxkk(x, kk),
-            (0x0d, _, _, _) => self.op_dxyn(x, y, n),
+            (0x0d, _, _, _) => self.op_dxyn(x, y, n)?,
             (0x0e, _, 0x09, 0x0e) => self.op_ex9e(x),
             (0x0e, _, 0x0a, 0x01) => self.op_exa1(x),
             (0x0f, _, 0x00, 0x07) => self.op_fx07(x),
@@ -145,26 +602,88 @@ impl Processor {
             (0x0f, _, 0x01, 0x08) => self.op_fx18(x),
             (0x0f, _, 0x01, 0x0e) => self.op_fx1e(x),
             (0x0f, _, 0x02, 0x09) => self.op_fx29(x),
-            (0x0f, _, 0x03, 0x03) => self.op_fx33(x),
-            (0x0f, _, 0x05, 0x05) => self.op_fx55(x),
-            (0x0f, _, 0x06, 0x05) => self.op_fx65(x),
-            _ => panic!("Unknown instruction")
+            (0x0f, _, 0x03, 0x00) => self.op_fx30(x),
+            (0x0f, _, 0x03, 0x03) => self.op_fx33(x)?,
+            (0x0f, _, 0x05, 0x05) => self.op_fx55(x)?,
+            (0x0f, _, 0x06, 0x05) => self.op_fx65(x)?,
+            (0x0f, _, 0x07, 0x05) => self.op_fx75(x),
+            (0x0f, _, 0x08, 0x05) => self.op_fx85(x),
+            _ => return Err(Chip8Error::BadInstruction(self.opcode)),
+        }
+
+        Ok(())
+    }
+
+    // Scroll the display down by n rows (SCHIP `00CN`).
+    fn op_00cn(&mut self, n: usize) {
+        let width = self.width();
+        let height = self.height();
+
+        for y in (0..height).rev() {
+            for x in 0..width {
+                self.video[y][x] = if y >= n { self.video[y - n][x] } else { 0 };
+            }
         }
     }
 
     // Clear the display.
     fn op_00e0(&mut self) {
-        for y in 0..SCREEN_HEIGHT {
-            for x in 0..SCREEN_WIDTH {
-                self.video[y][x] = 0;
+        for row in self.video.iter_mut() {
+            for pixel in row.iter_mut() {
+                *pixel = 0;
             }
         }
     }
 
     // Return from a subroutine.
-    fn op_00ee(&mut self) {
-        self.sp = self.sp - 1;
+    fn op_00ee(&mut self) -> Result<(), Chip8Error> {
+        if self.sp == 0 {
+            return Err(Chip8Error::StackUnderflow);
+        }
+
+        self.sp -= 1;
         self.pc = self.stack[self.sp as usize];
+
+        Ok(())
+    }
+
+    // Scroll the display right by 4 pixels (SCHIP `00FB`).
+    fn op_00fb(&mut self) {
+        let width = self.width();
+
+        for row in self.video.iter_mut() {
+            for x in (0..width).rev() {
+                row[x] = if x >= 4 { row[x - 4] } else { 0 };
+            }
+        }
+    }
+
+    // Scroll the display left by 4 pixels (SCHIP `00FC`).
+    fn op_00fc(&mut self) {
+        let width = self.width();
+
+        for row in self.video.iter_mut() {
+            for x in 0..width {
+                row[x] = if x + 4 < width { row[x + 4] } else { 0 };
+            }
+        }
+    }
+
+    // Exit the interpreter (SCHIP `00FD`).
+    fn op_00fd(&mut self) {
+        self.halted = true;
+    }
+
+    // Disable SCHIP hires mode, switching back to the 64x32 display.
+    fn op_00fe(&mut self) {
+        self.hires = false;
+        self.video = vec![vec![0; SCREEN_WIDTH]; SCREEN_HEIGHT];
+    }
+
+    // Enable SCHIP hires mode, switching to a 128x64 display.
+    fn op_00ff(&mut self) {
+        self.hires = true;
+        self.video = vec![vec![0; HIRES_SCREEN_WIDTH]; HIRES_SCREEN_HEIGHT];
     }
 
     // Jump to location nnn.
@@ -174,10 +693,16 @@ impl Processor {
     }
 
     // Call subroutine at nnn.
-    fn op_2nnn(&mut self, nnn: u16) {
+    fn op_2nnn(&mut self, nnn: u16) -> Result<(), Chip8Error> {
+        if self.sp as usize >= STACK_SIZE {
+            return Err(Chip8Error::StackOverflow);
+        }
+
         self.stack[self.sp as usize] = self.pc;
-        self.sp = self.sp + 1;
+        self.sp += 1;
         self.pc = nnn;
+
+        Ok(())
     }
 
     // Skip next instruction if Vx = kk.
@@ -222,16 +747,28 @@ impl Processor {
     // Set Vx = Vx OR Vy.
     fn op_8xy1(&mut self, x: usize, y: usize) {
         self.registers[x] = self.registers[x] | self.registers[y];
+
+        if self.quirks.vf_reset {
+            self.registers[CARRY_REGISTER] = 0;
+        }
     }
 
     // Set Vx = Vx AND Vy.
     fn op_8xy2(&mut self, x: usize, y: usize) {
         self.registers[x] = self.registers[x] & self.registers[y];
+
+        if self.quirks.vf_reset {
+            self.registers[CARRY_REGISTER] = 0;
+        }
     }
 
     // Set Vx = Vx XOR Vy.
     fn op_8xy3(&mut self, x: usize, y: usize) {
         self.registers[x] = self.registers[x] ^ self.registers[y];
+
+        if self.quirks.vf_reset {
+            self.registers[CARRY_REGISTER] = 0;
+        }
     }
 
     // Set Vx = Vx + Vy, set VF = carry.
@@ -254,13 +791,15 @@ impl Processor {
         self.registers[x] = self.registers[x].wrapping_sub(self.registers[y]);
     }
 
-    // Set Vx = Vx SHR 1.
-    // If the least-significant bit of Vx is 1, then VF is set to 1, otherwise 0.
-    // Then Vx is divided by 2.
-    fn op_8xy6(&mut self, x: usize) {
-        self.registers[CARRY_REGISTER] = self.registers[x] & 0x1;
+    // Set Vx = Vx SHR 1 (or Vy SHR 1 under the shift quirk).
+    // If the least-significant bit of the source register is 1, then VF is set to 1, otherwise 0.
+    // Then the result is divided by 2.
+    fn op_8xy6(&mut self, x: usize, y: usize) {
+        let source = if self.quirks.shift { y } else { x };
+
+        self.registers[CARRY_REGISTER] = self.registers[source] & 0x1;
 
-        self.registers[x] = self.registers[x] >> 1;
+        self.registers[x] = self.registers[source] >> 1;
     }
 
     // Set Vx = Vy - Vx, set VF = NOT borrow.
@@ -271,12 +810,14 @@ impl Processor {
         self.registers[x] = self.registers[y].wrapping_sub(self.registers[x]);
     }
 
-    // Set Vx = Vx SHL 1.
-    // If the most-significant bit of Vx is 1, then VF is set to 1, otherwise to 0.
-    // Then Vx is multiplied by 2.
-    fn op_8xye(&mut self, x: usize) {
-        self.registers[CARRY_REGISTER] = (self.registers[x] & 0b10000000) >> 7;
-        self.registers[x] <<= 1;
+    // Set Vx = Vx SHL 1 (or Vy SHL 1 under the shift quirk).
+    // If the most-significant bit of the source register is 1, then VF is set to 1, otherwise to 0.
+    // Then the result is multiplied by 2.
+    fn op_8xye(&mut self, x: usize, y: usize) {
+        let source = if self.quirks.shift { y } else { x };
+
+        self.registers[CARRY_REGISTER] = (self.registers[source] & 0b10000000) >> 7;
+        self.registers[x] = self.registers[source] << 1;
     }
 
     // Skip next instruction if Vx != Vy.
@@ -291,9 +832,11 @@ impl Processor {
         self.index = nnn
     }
 
-    // Jump to location nnn + V0.
-    fn op_bnnn(&mut self, nnn: u16) {
-        self.pc = self.registers[0] as u16 + nnn;
+    // Jump to location nnn + V0 (or, under the jump quirk, to xnn + Vx).
+    fn op_bnnn(&mut self, x: usize, nnn: u16) {
+        let base_register = if self.quirks.jump { x } else { 0 };
+
+        self.pc = self.registers[base_register] as u16 + nnn;
     }
 
     // Set Vx = random byte AND kk.
@@ -312,17 +855,78 @@ impl Processor {
     // it is set to 0. If the sprite is positioned so part of it is outside
     // the coordinates of the display, it wraps around to the opposite side
     // of the screen.
-    fn op_dxyn(&mut self, x: usize, y: usize, n: usize) {
+    // Under the clipping quirk, those parts are skipped instead of wrapped.
+    fn op_dxyn(&mut self, x: usize, y: usize, n: usize) -> Result<(), Chip8Error> {
+        if self.hires && n == 0 {
+            return self.draw_sprite_16x16(x, y);
+        }
+
+        if self.index as usize + n > MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.index));
+        }
+
+        let width = self.width();
+        let height = self.height();
+
         self.registers[CARRY_REGISTER] = 0;
         for byte in 0..n {
-            let y = (self.registers[y] as usize + byte) % SCREEN_HEIGHT;
+            let y = self.registers[y] as usize + byte;
+            if self.quirks.clipping && y >= height {
+                continue;
+            }
+            let y = y % height;
+
             for bit in 0..8 {
-                let x = (self.registers[x] as usize + bit) % SCREEN_WIDTH;
+                let x = self.registers[x] as usize + bit;
+                if self.quirks.clipping && x >= width {
+                    continue;
+                }
+                let x = x % width;
+
                 let color = (self.memory[self.index as usize + byte] >> (7 - bit)) & 1;
                 self.registers[CARRY_REGISTER] |= color & self.video[y][x];
                 self.video[y][x] ^= color;
             }
         }
+
+        Ok(())
+    }
+
+    // Display a 16x16 sprite (SCHIP hires mode), reading 32 bytes from memory starting at I.
+    fn draw_sprite_16x16(&mut self, x: usize, y: usize) -> Result<(), Chip8Error> {
+        if self.index as usize + 32 > MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.index));
+        }
+
+        let width = self.width();
+        let height = self.height();
+
+        self.registers[CARRY_REGISTER] = 0;
+        for row in 0..16 {
+            let y = self.registers[y] as usize + row;
+            if self.quirks.clipping && y >= height {
+                continue;
+            }
+            let y = y % height;
+
+            let high_byte = self.memory[self.index as usize + row * 2] as u16;
+            let low_byte = self.memory[self.index as usize + row * 2 + 1] as u16;
+            let bits = (high_byte << 8) | low_byte;
+
+            for bit in 0..16 {
+                let x = self.registers[x] as usize + bit;
+                if self.quirks.clipping && x >= width {
+                    continue;
+                }
+                let x = x % width;
+
+                let color = ((bits >> (15 - bit)) & 1) as u8;
+                self.registers[CARRY_REGISTER] |= color & self.video[y][x];
+                self.video[y][x] ^= color;
+            }
+        }
+
+        Ok(())
     }
 
     // Skip next instruction if key with the value of Vx is pressed.
@@ -387,28 +991,213 @@ impl Processor {
         self.index = (FONTSET_START_ADDRESS + FONT_CHARACTER_BYTES * digit) as u16;
     }
 
+    // Set I = location of the 8x10 large sprite for digit Vx (SCHIP `FX30`).
+    fn op_fx30(&mut self, x: usize) {
+        let digit = self.registers[x] as u16;
+
+        self.index = BIG_FONTSET_START_ADDRESS + BIG_FONT_CHARACTER_BYTES as u16 * digit;
+    }
+
     // Store BCD representation of Vx in memory locations I, I+1, and I+2.
     // The interpreter takes the decimal value of Vx, and places the hundreds digit in memory at
     // location in I, the tens digit at location I+1, and the ones digit at location I+2.
-    fn op_fx33(&mut self, x: usize) {
+    fn op_fx33(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.index as usize + 2 >= MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.index));
+        }
+
         let vx = self.registers[x];
         self.memory[self.index as usize] = (vx / 100) as u8;
         self.memory[self.index as usize + 1] = ((vx % 100) / 10) as u8;
         self.memory[self.index as usize + 2] = (vx % 10) as u8;
+
+        Ok(())
     }
 
     // Store registers V0 through Vx in memory starting at location I.
-    fn op_fx55(&mut self, x: usize) {
+    fn op_fx55(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.index as usize + x >= MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.index));
+        }
+
         for i in 0..x + 1 {
             self.memory[self.index as usize + i] = self.registers[i];
         }
+
+        if self.quirks.load_store {
+            self.index += x as u16 + 1;
+        }
+
+        Ok(())
     }
 
     // Read registers V0 through Vx from memory starting at location I.
-    fn op_fx65(&mut self, x: usize) {
+    fn op_fx65(&mut self, x: usize) -> Result<(), Chip8Error> {
+        if self.index as usize + x >= MEMORY_SIZE {
+            return Err(Chip8Error::AddressOutOfBounds(self.index));
+        }
+
         for i in 0..x + 1 {
             self.registers[i] = self.memory[self.index as usize + i];
         }
+
+        if self.quirks.load_store {
+            self.index += x as u16 + 1;
+        }
+
+        Ok(())
+    }
+
+    // Store V0 through Vx into the persistent RPL flags (SCHIP `FX75`).
+    fn op_fx75(&mut self, x: usize) {
+        for i in 0..x + 1 {
+            self.rpl_flags[i] = self.registers[i];
+        }
+    }
+
+    // Read V0 through Vx back from the persistent RPL flags (SCHIP `FX85`).
+    fn op_fx85(&mut self, x: usize) {
+        for i in 0..x + 1 {
+            self.registers[i] = self.rpl_flags[i];
+        }
+    }
+
+}
+
+/// Renders `opcode` as a human-readable mnemonic without executing it, for a debugger UI or
+/// [`Processor::step`]'s `TraceRecord`.
+fn disassemble(opcode: u16) -> String {
+    let n1 = (opcode & 0xF000) >> 12;
+    let n2 = (opcode & 0x0F00) >> 8;
+    let n3 = (opcode & 0x00F0) >> 4;
+    let n4 = opcode & 0x000F;
+
+    let nnn = opcode & 0x0FFF;
+    let kk = opcode & 0x00FF;
+    let x = n2;
+    let y = n3;
+    let n = n4;
+
+    match (n1, n2, n3, n4) {
+        (0x0, 0x0, 0xC, _) => format!("SCD {}", n),
+        (0x0, 0x0, 0xE, 0x0) => "CLS".to_string(),
+        (0x0, 0x0, 0xE, 0xE) => "RET".to_string(),
+        (0x0, 0x0, 0xF, 0xB) => "SCR".to_string(),
+        (0x0, 0x0, 0xF, 0xC) => "SCL".to_string(),
+        (0x0, 0x0, 0xF, 0xD) => "EXIT".to_string(),
+        (0x0, 0x0, 0xF, 0xE) => "LOW".to_string(),
+        (0x0, 0x0, 0xF, 0xF) => "HIGH".to_string(),
+        (0x1, _, _, _) => format!("JP 0x{:03X}", nnn),
+        (0x2, _, _, _) => format!("CALL 0x{:03X}", nnn),
+        (0x3, _, _, _) => format!("SE V{:X}, 0x{:02X}", x, kk),
+        (0x4, _, _, _) => format!("SNE V{:X}, 0x{:02X}", x, kk),
+        (0x5, _, _, 0x0) => format!("SE V{:X}, V{:X}", x, y),
+        (0x6, _, _, _) => format!("LD V{:X}, 0x{:02X}", x, kk),
+        (0x7, _, _, _) => format!("ADD V{:X}, 0x{:02X}", x, kk),
+        (0x8, _, _, 0x0) => format!("LD V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x1) => format!("OR V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x2) => format!("AND V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x3) => format!("XOR V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x4) => format!("ADD V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x5) => format!("SUB V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0x6) => format!("SHR V{:X}", x),
+        (0x8, _, _, 0x7) => format!("SUBN V{:X}, V{:X}", x, y),
+        (0x8, _, _, 0xE) => format!("SHL V{:X}", x),
+        (0x9, _, _, 0x0) => format!("SNE V{:X}, V{:X}", x, y),
+        (0xA, _, _, _) => format!("LD I, 0x{:03X}", nnn),
+        (0xB, _, _, _) => format!("JP V0, 0x{:03X}", nnn),
+        (0xC, _, _, _) => format!("RND V{:X}, 0x{:02X}", x, kk),
+        (0xD, _, _, _) => format!("DRW V{:X}, V{:X}, {}", x, y, n),
+        (0xE, _, 0x9, 0xE) => format!("SKP V{:X}", x),
+        (0xE, _, 0xA, 0x1) => format!("SKNP V{:X}", x),
+        (0xF, _, 0x0, 0x7) => format!("LD V{:X}, DT", x),
+        (0xF, _, 0x0, 0xA) => format!("LD V{:X}, K", x),
+        (0xF, _, 0x1, 0x5) => format!("LD DT, V{:X}", x),
+        (0xF, _, 0x1, 0x8) => format!("LD ST, V{:X}", x),
+        (0xF, _, 0x1, 0xE) => format!("ADD I, V{:X}", x),
+        (0xF, _, 0x2, 0x9) => format!("LD F, V{:X}", x),
+        (0xF, _, 0x3, 0x0) => format!("LD HF, V{:X}", x),
+        (0xF, _, 0x3, 0x3) => format!("LD B, V{:X}", x),
+        (0xF, _, 0x5, 0x5) => format!("LD [I], V{:X}", x),
+        (0xF, _, 0x6, 0x5) => format!("LD V{:X}, [I]", x),
+        (0xF, _, 0x7, 0x5) => format!("LD R, V{:X}", x),
+        (0xF, _, 0x8, 0x5) => format!("LD V{:X}, R", x),
+        _ => format!("DW 0x{:04X}", opcode),
+    }
+}
+
+/// Appends `data` to `buf` prefixed with its length, for [`ProcessorState::to_bytes`].
+fn write_field(buf: &mut Vec<u8>, data: &[u8]) {
+    buf.extend_from_slice(&(data.len() as u32).to_be_bytes());
+    buf.extend_from_slice(data);
+}
+
+/// Reads a length-prefixed field out of `bytes` at `cursor`, checking the declared
+/// length matches `expected_len` and that enough bytes remain, for [`ProcessorState::from_bytes`].
+fn read_field<'a>(bytes: &'a [u8], cursor: &mut usize, expected_len: usize) -> Result<&'a [u8], StateError> {
+    if *cursor + 4 > bytes.len() {
+        return Err(StateError::Truncated);
     }
 
+    let len = u32::from_be_bytes([
+        bytes[*cursor],
+        bytes[*cursor + 1],
+        bytes[*cursor + 2],
+        bytes[*cursor + 3],
+    ]) as usize;
+    *cursor += 4;
+
+    if len != expected_len || *cursor + len > bytes.len() {
+        return Err(StateError::Truncated);
+    }
+
+    let field = &bytes[*cursor..*cursor + len];
+    *cursor += len;
+
+    Ok(field)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn state_round_trips_through_bytes() {
+        let mut processor = Processor::new();
+        processor.registers[3] = 0x42;
+        processor.pc = 0x300;
+        processor.index = 0x123;
+        processor.video[0][0] = 1;
+
+        let bytes = processor.to_bytes();
+        let restored = Processor::from_bytes(&bytes).unwrap();
+
+        assert_eq!(restored.registers, processor.registers);
+        assert_eq!(restored.pc, processor.pc);
+        assert_eq!(restored.index, processor.index);
+        assert_eq!(restored.video, processor.video);
+    }
+
+    #[test]
+    fn from_bytes_rejects_malformed_dimensions() {
+        let mut state = Processor::new().snapshot();
+        state.video = vec![vec![0; 7]; 7];
+
+        let bytes = state.to_bytes();
+
+        assert!(matches!(
+            ProcessorState::from_bytes(&bytes),
+            Err(StateError::InvalidDimensions { width: 7, height: 7 })
+        ));
+    }
+
+    #[test]
+    fn from_bytes_rejects_truncated_blob() {
+        let bytes = Processor::new().to_bytes();
+
+        assert!(matches!(
+            ProcessorState::from_bytes(&bytes[..bytes.len() - 1]),
+            Err(StateError::Truncated)
+        ));
+    }
 }
\ No newline at end of file